@@ -1,36 +1,108 @@
 use anyhow::{anyhow, Context, Result};
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 use crate::opts::Opts;
+use crate::store::{self, Store};
 
 #[derive(Debug)]
 pub struct Config {
     pub operation: Operation,
     pub pwd: PathBuf,
     pub config: PathBuf,
+    pub format: ConfigFormat,
 }
 
 impl TryFrom<Opts> for Config {
     type Error = anyhow::Error;
 
     fn try_from(value: Opts) -> Result<Self> {
-        let operation = value.args.try_into()?;
-        let config = get_config(value.config)?;
+        let (config, format) = get_config(value.config)?;
         let pwd = get_pwd(value.pwd)?;
 
+        let store = store::load(&config, format)?;
+        let args = expand_aliases(value.args, &store)?;
+        let operation = args.try_into()?;
+
         return Ok(Config {
             operation,
             pwd,
             config,
+            format,
         });
     }
 }
 
+/// Resolves the store's `alias` table against the first token of `args`,
+/// splicing in the alias's whitespace-split expansion in its place. Mirrors
+/// cargo's `aliased_command`: unknown first tokens pass through untouched,
+/// and an alias that (directly or transitively) expands back to itself is
+/// rejected rather than looped on forever.
+fn expand_aliases(mut args: Vec<String>, store: &Store) -> Result<Vec<String>> {
+    let mut expanded = HashSet::new();
+
+    loop {
+        let Some(first) = args.get(0) else {
+            return Ok(args);
+        };
+
+        let Some(expansion) = store.alias.get(first) else {
+            return Ok(args);
+        };
+
+        if !expanded.insert(first.clone()) {
+            return Err(anyhow!("encountered alias cycle while expanding '{}'", first));
+        }
+
+        let mut tokens: Vec<String> = expansion.split_whitespace().map(String::from).collect();
+        tokens.extend(args.drain(1..));
+        args = tokens;
+    }
+}
+
+/// The serialization format of a `projector` config file, selected by file
+/// extension so the writer half can round-trip whatever it read.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Formats are probed in this order when no explicit `--config` is given.
+    const PROBE_ORDER: [ConfigFormat; 3] =
+        [ConfigFormat::Json, ConfigFormat::Toml, ConfigFormat::Yaml];
+
+    fn file_name(self) -> &'static str {
+        match self {
+            ConfigFormat::Json => "projector.json",
+            ConfigFormat::Toml => "projector.toml",
+            ConfigFormat::Yaml => "projector.yaml",
+        }
+    }
+
+    fn from_path(path: &Path) -> Option<ConfigFormat> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Some(ConfigFormat::Json),
+            Some("toml") => Some(ConfigFormat::Toml),
+            Some("yaml") | Some("yml") => Some(ConfigFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Operation {
     Print(Option<String>),
     Add(String, String),
     Remove(String),
+    /// Like `Print`, but reports which directory's config supplied the value.
+    Locate(String),
+    /// Executes the stored `cmd.<name>` command in the resolved project directory.
+    Run(String),
+    /// Like `Run`, but in every directory that defines the named command.
+    RunAll(String),
 }
 
 impl TryFrom<Vec<String>> for Operation {
@@ -73,6 +145,46 @@ impl TryFrom<Vec<String>> for Operation {
             return Ok(Operation::Remove(arg));
         }
 
+        if term == "where" || term == "locate" {
+            if value.len() != 2 {
+                return Err(anyhow!(
+                    "operation {} expects 1 argument but got {}",
+                    term,
+                    value.len() - 1
+                ));
+            }
+
+            let arg = value.pop().expect("to exist");
+
+            return Ok(Operation::Locate(arg));
+        }
+
+        if term == "run" {
+            if value.len() != 2 {
+                return Err(anyhow!(
+                    "operation run expects 1 argument but got {}",
+                    value.len() - 1
+                ));
+            }
+
+            let arg = value.pop().expect("to exist");
+
+            return Ok(Operation::Run(arg));
+        }
+
+        if term == "run-all" {
+            if value.len() != 2 {
+                return Err(anyhow!(
+                    "operation run-all expects 1 argument but got {}",
+                    value.len() - 1
+                ));
+            }
+
+            let arg = value.pop().expect("to exist");
+
+            return Ok(Operation::RunAll(arg));
+        }
+
         if value.len() > 1 {
             return Err(anyhow!(
                 "operation print expects 0 or 1 arguments but got {}",
@@ -86,18 +198,62 @@ impl TryFrom<Vec<String>> for Operation {
     }
 }
 
-fn get_config(config: Option<PathBuf>) -> Result<PathBuf> {
+/// The full answer to `Operation::Locate`: the resolved value, the directory
+/// whose own config supplied it, and the absolute config file it came from.
+#[derive(Debug, PartialEq)]
+pub struct LocateReport {
+    pub value: String,
+    pub source: PathBuf,
+    pub config: PathBuf,
+}
+
+/// Walks the directory hierarchy from the filesystem root down to `pwd`,
+/// resolving `key` the same way `Print` does, but also reporting which
+/// ancestor directory's entry actually supplied the value. Returns `None`
+/// if no directory on the way to `pwd` defines `key`.
+pub fn locate(store: &Store, pwd: &Path, config: &Path, key: &str) -> Option<LocateReport> {
+    let resolved = store::locate(store, pwd, key)?;
+
+    let config = if config.is_absolute() {
+        config.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map(|dir| dir.join(config))
+            .unwrap_or_else(|_| config.to_path_buf())
+    };
+
+    return Some(LocateReport {
+        value: resolved.value,
+        source: resolved.source,
+        config,
+    });
+}
+
+fn get_config(config: Option<PathBuf>) -> Result<(PathBuf, ConfigFormat)> {
     if let Some(v) = config {
-        return Ok(v);
+        let format = ConfigFormat::from_path(&v)
+            .ok_or_else(|| anyhow!("unrecognized config file extension: {}", v.display()))?;
+
+        return Ok((v, format));
     }
 
     let loc = std::env::var("XDG_CONFIG_HOME").context("unable to get XDG_CONFIG_HOME")?;
-    let mut loc = PathBuf::from(loc);
+    let mut dir = PathBuf::from(loc);
+
+    dir.push("projector");
 
-    loc.push("projector");
-    loc.push("projector.json");
+    for format in ConfigFormat::PROBE_ORDER {
+        let candidate = dir.join(format.file_name());
 
-    return Ok(loc);
+        if candidate.exists() {
+            return Ok((candidate, format));
+        }
+    }
+
+    let format = ConfigFormat::PROBE_ORDER[0];
+    let loc = dir.join(format.file_name());
+
+    return Ok((loc, format));
 }
 
 fn get_pwd(pwd: Option<PathBuf>) -> Result<PathBuf> {
@@ -111,10 +267,11 @@ fn get_pwd(pwd: Option<PathBuf>) -> Result<PathBuf> {
 #[cfg(test)]
 mod test {
     use anyhow::Result;
+    use std::path::{Path, PathBuf};
 
-    use crate::{config::Operation, opts::Opts};
+    use crate::{config::Operation, opts::Opts, store::Store};
 
-    use super::Config;
+    use super::{Config, ConfigFormat};
 
     #[test]
     fn test_print_all() -> Result<()> {
@@ -180,4 +337,238 @@ mod test {
 
         return Ok(());
     }
+
+    #[test]
+    fn test_where_key() -> Result<()> {
+        let locate = String::from("where");
+        let foo = String::from("foo");
+
+        let opts: Config = Opts {
+            args: vec![locate, foo.clone()],
+            pwd: None,
+            config: None,
+        }
+        .try_into()?;
+
+        assert_eq!(opts.operation, Operation::Locate(foo));
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_locate_key() -> Result<()> {
+        let locate = String::from("locate");
+        let foo = String::from("foo");
+
+        let opts: Config = Opts {
+            args: vec![locate, foo.clone()],
+            pwd: None,
+            config: None,
+        }
+        .try_into()?;
+
+        assert_eq!(opts.operation, Operation::Locate(foo));
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_locate_reports_nearest_defining_ancestor() {
+        let mut store = Store::default();
+
+        let mut root = crate::store::DirConfig::default();
+        root.entries
+            .insert(String::from("lang"), String::from("rust"));
+        store.projector.insert(String::from("/"), root);
+
+        let mut home = crate::store::DirConfig::default();
+        home.entries
+            .insert(String::from("lang"), String::from("python"));
+        store.projector.insert(String::from("/home/user"), home);
+
+        let mut project = crate::store::DirConfig::default();
+        project
+            .entries
+            .insert(String::from("editor"), String::from("vim"));
+        store
+            .projector
+            .insert(String::from("/home/user/project"), project);
+
+        let config_path = PathBuf::from("/home/user/.config/projector/projector.json");
+
+        let report = super::locate(
+            &store,
+            Path::new("/home/user/project"),
+            &config_path,
+            "lang",
+        )
+        .expect("lang should resolve from an ancestor");
+
+        assert_eq!(report.value, "python");
+        assert_eq!(report.source, PathBuf::from("/home/user"));
+        assert_eq!(report.config, config_path);
+
+        let inherited = super::locate(
+            &store,
+            Path::new("/home/user/project"),
+            &config_path,
+            "editor",
+        )
+        .expect("editor should resolve from the project directory itself");
+
+        assert_eq!(inherited.value, "vim");
+        assert_eq!(inherited.source, PathBuf::from("/home/user/project"));
+
+        let root_only = super::locate(&store, Path::new("/home/other"), &config_path, "lang")
+            .expect("lang should fall back to the root entry");
+
+        assert_eq!(root_only.value, "rust");
+        assert_eq!(root_only.source, PathBuf::from("/"));
+
+        assert!(super::locate(&store, Path::new("/home/other"), &config_path, "editor").is_none());
+    }
+
+    #[test]
+    fn test_config_format_from_json_extension() -> Result<()> {
+        let opts: Config = Opts {
+            args: vec![],
+            pwd: Some(PathBuf::from("/tmp")),
+            config: Some(PathBuf::from("/tmp/projector.json")),
+        }
+        .try_into()?;
+
+        assert_eq!(opts.format, ConfigFormat::Json);
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_config_format_from_toml_extension() -> Result<()> {
+        let opts: Config = Opts {
+            args: vec![],
+            pwd: Some(PathBuf::from("/tmp")),
+            config: Some(PathBuf::from("/tmp/projector.toml")),
+        }
+        .try_into()?;
+
+        assert_eq!(opts.format, ConfigFormat::Toml);
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_config_format_from_yaml_extension() -> Result<()> {
+        let opts: Config = Opts {
+            args: vec![],
+            pwd: Some(PathBuf::from("/tmp")),
+            config: Some(PathBuf::from("/tmp/projector.yaml")),
+        }
+        .try_into()?;
+
+        assert_eq!(opts.format, ConfigFormat::Yaml);
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_config_format_rejects_unknown_extension() {
+        let result: Result<Config> = Opts {
+            args: vec![],
+            pwd: Some(PathBuf::from("/tmp")),
+            config: Some(PathBuf::from("/tmp/projector.ini")),
+        }
+        .try_into();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_alias_single_token() -> Result<()> {
+        let mut store = Store::default();
+        store.alias.insert(String::from("ls"), String::from("print"));
+
+        let args = super::expand_aliases(vec![String::from("ls")], &store)?;
+
+        assert_eq!(args, vec![String::from("print")]);
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_alias_multi_token() -> Result<()> {
+        let mut store = Store::default();
+        store
+            .alias
+            .insert(String::from("setpy"), String::from("add python"));
+
+        let args =
+            super::expand_aliases(vec![String::from("setpy"), String::from("3.10")], &store)?;
+
+        assert_eq!(
+            args,
+            vec![
+                String::from("add"),
+                String::from("python"),
+                String::from("3.10"),
+            ]
+        );
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_alias_unknown_passthrough() -> Result<()> {
+        let store = Store::default();
+
+        let args = super::expand_aliases(vec![String::from("print")], &store)?;
+
+        assert_eq!(args, vec![String::from("print")]);
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_run_key() -> Result<()> {
+        let run = String::from("run");
+        let name = String::from("build");
+
+        let opts: Config = Opts {
+            args: vec![run, name.clone()],
+            pwd: None,
+            config: None,
+        }
+        .try_into()?;
+
+        assert_eq!(opts.operation, Operation::Run(name));
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_run_all_key() -> Result<()> {
+        let run_all = String::from("run-all");
+        let name = String::from("build");
+
+        let opts: Config = Opts {
+            args: vec![run_all, name.clone()],
+            pwd: None,
+            config: None,
+        }
+        .try_into()?;
+
+        assert_eq!(opts.operation, Operation::RunAll(name));
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_alias_cycle_rejected() {
+        let mut store = Store::default();
+        store.alias.insert(String::from("a"), String::from("b"));
+        store.alias.insert(String::from("b"), String::from("a"));
+
+        let result = super::expand_aliases(vec![String::from("a")], &store);
+
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,162 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::store::{self, Store};
+
+/// Spawns `command` inside `pwd`, inheriting stdio, and returns its exit code.
+fn spawn(command: &str, pwd: &Path) -> Result<i32> {
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(pwd)
+        .status()
+        .with_context(|| format!("failed spawning '{}' in {}", command, pwd.display()))?;
+
+    return Ok(status.code().unwrap_or(1));
+}
+
+/// Runs the stored command `name`, resolved for `pwd` via the directory
+/// hierarchy merge, inheriting stdio and propagating the child's exit code.
+/// The command is run as stored: `${...}` placeholders in it are not expanded.
+pub fn run(name: &str, pwd: &Path, store: &Store) -> Result<i32> {
+    let resolved = store::resolve_cmds(store, pwd);
+
+    let command = resolved
+        .get(name)
+        .ok_or_else(|| anyhow!("no command named '{}' is defined", name))?;
+
+    return spawn(&command.value, pwd);
+}
+
+/// One directory's outcome from a `RunAll` pass.
+#[derive(Debug)]
+pub struct RunReport {
+    pub pwd: PathBuf,
+    pub outcome: RunOutcome,
+}
+
+impl RunReport {
+    pub fn success(&self) -> bool {
+        return matches!(self.outcome, RunOutcome::Exited(0));
+    }
+}
+
+/// How a single directory's command ended up: a process that ran to
+/// completion (possibly with a non-zero exit code), or one that never
+/// started at all.
+#[derive(Debug)]
+pub enum RunOutcome {
+    Exited(i32),
+    Failed(String),
+}
+
+/// Runs `name` sequentially in every directory that defines its own
+/// `cmd.<name>`, continuing past failures so a single broken project doesn't
+/// hide the rest of the report.
+pub fn run_all(name: &str, store: &Store) -> Vec<RunReport> {
+    let mut reports = Vec::new();
+
+    for (dir, config) in &store.projector {
+        let Some(command) = config.cmd.get(name) else {
+            continue;
+        };
+
+        let pwd = PathBuf::from(dir);
+
+        let outcome = match spawn(command, &pwd) {
+            Ok(code) => RunOutcome::Exited(code),
+            Err(err) => RunOutcome::Failed(err.to_string()),
+        };
+
+        reports.push(RunReport { pwd, outcome });
+    }
+
+    return reports;
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+
+    use crate::store::DirConfig;
+
+    use super::*;
+
+    #[test]
+    fn test_run_executes_stored_command() -> Result<()> {
+        let mut store = Store::default();
+
+        let mut project = DirConfig::default();
+        project
+            .cmd
+            .insert(String::from("ok"), String::from("exit 0"));
+        store
+            .projector
+            .insert(std::env::temp_dir().to_string_lossy().into_owned(), project);
+
+        let code = run("ok", &std::env::temp_dir(), &store)?;
+
+        assert_eq!(code, 0);
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_run_missing_command_errors() {
+        let store = Store::default();
+
+        let result = run("missing", &std::env::temp_dir(), &store);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_run_all_skips_directories_without_the_command() {
+        let mut store = Store::default();
+
+        let mut has_it = DirConfig::default();
+        has_it
+            .cmd
+            .insert(String::from("ok"), String::from("exit 0"));
+        store.projector.insert(String::from("/tmp/has-it"), has_it);
+
+        store
+            .projector
+            .insert(String::from("/tmp/without-it"), DirConfig::default());
+
+        let reports = run_all("ok", &store);
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].pwd, PathBuf::from("/tmp/has-it"));
+        assert!(reports[0].success());
+    }
+
+    #[test]
+    fn test_run_all_reports_spawn_failures_without_aborting() {
+        let mut store = Store::default();
+
+        let mut broken = DirConfig::default();
+        broken
+            .cmd
+            .insert(String::from("ok"), String::from("exit 0"));
+        store
+            .projector
+            .insert(String::from("/this/directory/does/not/exist"), broken);
+
+        let mut working = DirConfig::default();
+        working
+            .cmd
+            .insert(String::from("ok"), String::from("exit 0"));
+        store
+            .projector
+            .insert(std::env::temp_dir().to_string_lossy().into_owned(), working);
+
+        let reports = run_all("ok", &store);
+
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().any(|r| !r.success()));
+        assert!(reports.iter().any(|r| r.success()));
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigFormat;
+
+/// A single directory's own config: the plain key/values it defines, plus
+/// the per-project `cmd.*` commands `spawn` can run there.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DirConfig {
+    #[serde(default)]
+    pub entries: BTreeMap<String, String>,
+    #[serde(default)]
+    pub cmd: BTreeMap<String, String>,
+}
+
+/// The full contents of a config file: directory-scoped entries (the thing
+/// the root-to-`pwd` hierarchy merge walks) plus the global `alias` table.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Store {
+    #[serde(default)]
+    pub alias: BTreeMap<String, String>,
+    #[serde(default)]
+    pub projector: BTreeMap<String, DirConfig>,
+}
+
+/// A value resolved for a `pwd`, together with the directory whose own
+/// config actually supplied it (the nearest ancestor that defines it).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolved {
+    pub value: String,
+    pub source: PathBuf,
+}
+
+/// Reads the store at `path`, returning an empty store if it doesn't exist yet.
+pub fn load(path: &Path, format: ConfigFormat) -> Result<Store> {
+    if !path.exists() {
+        return Ok(Store::default());
+    }
+
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("failed reading {}", path.display()))?;
+
+    let store = match format {
+        ConfigFormat::Json => serde_json::from_str(&raw)
+            .with_context(|| format!("failed parsing json config at {}", path.display()))?,
+        ConfigFormat::Toml => toml::from_str(&raw)
+            .with_context(|| format!("failed parsing toml config at {}", path.display()))?,
+        ConfigFormat::Yaml => serde_yaml::from_str(&raw)
+            .with_context(|| format!("failed parsing yaml config at {}", path.display()))?,
+    };
+
+    return Ok(store);
+}
+
+/// Writes `store` to `path` in the same format it was read from.
+pub fn save(path: &Path, format: ConfigFormat, store: &Store) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed creating {}", parent.display()))?;
+    }
+
+    let raw = match format {
+        ConfigFormat::Json => serde_json::to_string_pretty(store)?,
+        ConfigFormat::Toml => toml::to_string_pretty(store)?,
+        ConfigFormat::Yaml => serde_yaml::to_string(store)?,
+    };
+
+    fs::write(path, raw).with_context(|| format!("failed writing {}", path.display()))?;
+
+    return Ok(());
+}
+
+/// The key a directory is addressed by in `Store::projector`.
+fn dir_key(dir: &Path) -> String {
+    return dir.to_string_lossy().into_owned();
+}
+
+/// `pwd`'s ancestors from the filesystem root down to `pwd` itself, the order
+/// the hierarchy merge walks in so that the nearest directory wins.
+fn ancestors_root_to_leaf(pwd: &Path) -> Vec<PathBuf> {
+    let mut chain: Vec<PathBuf> = pwd.ancestors().map(Path::to_path_buf).collect();
+    chain.reverse();
+
+    return chain;
+}
+
+/// Merges `select`'s view of every ancestor directory's config from the
+/// filesystem root down to `pwd`, recording which directory last set each key.
+fn merge<'a>(
+    store: &'a Store,
+    pwd: &Path,
+    select: impl Fn(&'a DirConfig) -> &'a BTreeMap<String, String>,
+) -> BTreeMap<String, Resolved> {
+    let mut merged = BTreeMap::new();
+
+    for dir in ancestors_root_to_leaf(pwd) {
+        let Some(config) = store.projector.get(&dir_key(&dir)) else {
+            continue;
+        };
+
+        for (key, value) in select(config) {
+            merged.insert(
+                key.clone(),
+                Resolved {
+                    value: value.clone(),
+                    source: dir.clone(),
+                },
+            );
+        }
+    }
+
+    return merged;
+}
+
+/// Resolves every plain key/value visible from `pwd`, nearest directory wins.
+pub fn resolve_entries(store: &Store, pwd: &Path) -> BTreeMap<String, Resolved> {
+    return merge(store, pwd, |config| &config.entries);
+}
+
+/// Resolves `key` for `pwd`, reporting the directory whose config supplied it.
+pub fn locate(store: &Store, pwd: &Path, key: &str) -> Option<Resolved> {
+    return resolve_entries(store, pwd).remove(key);
+}
+
+/// Resolves every `cmd.*` command visible from `pwd`, nearest directory wins.
+pub fn resolve_cmds(store: &Store, pwd: &Path) -> BTreeMap<String, Resolved> {
+    return merge(store, pwd, |config| &config.cmd);
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn sample() -> Store {
+        let mut store = Store::default();
+
+        store.alias.insert(String::from("ls"), String::from("print"));
+        store
+            .alias
+            .insert(String::from("setpy"), String::from("add python"));
+
+        let mut project = DirConfig::default();
+        project
+            .entries
+            .insert(String::from("foo"), String::from("bar"));
+        project
+            .cmd
+            .insert(String::from("build"), String::from("cargo build"));
+
+        store
+            .projector
+            .insert(String::from("/home/user/project"), project);
+
+        return store;
+    }
+
+    fn roundtrip(format: ConfigFormat, file_name: &str) -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join(file_name);
+        let store = sample();
+
+        save(&path, format, &store)?;
+        let loaded = load(&path, format)?;
+
+        assert_eq!(loaded, store);
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_json_roundtrip() -> Result<()> {
+        return roundtrip(ConfigFormat::Json, "projector.json");
+    }
+
+    #[test]
+    fn test_toml_roundtrip() -> Result<()> {
+        return roundtrip(ConfigFormat::Toml, "projector.toml");
+    }
+
+    #[test]
+    fn test_yaml_roundtrip() -> Result<()> {
+        return roundtrip(ConfigFormat::Yaml, "projector.yaml");
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("projector.json");
+
+        assert_eq!(load(&path, ConfigFormat::Json)?, Store::default());
+
+        return Ok(());
+    }
+}
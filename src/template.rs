@@ -0,0 +1,195 @@
+use anyhow::{anyhow, Result};
+
+use crate::config::Config;
+
+/// Expands `${VAR}` references in `raw` against the process environment and
+/// the built-in `${PWD}` / `${CONFIG_DIR}` placeholders. `spawn::run` does
+/// not call it. `$$` is a literal dollar escape. A placeholder's name
+/// may itself contain placeholders (e.g. `${${X}}`), which are expanded
+/// first to find the name actually being looked up. Unknown placeholders
+/// are left intact; pass `strict: true` to error on them instead.
+pub fn expand(raw: &str, config: &Config, strict: bool) -> Result<String> {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+
+        if chars.peek() == Some(&'$') {
+            chars.next();
+            out.push('$');
+            continue;
+        }
+
+        if chars.peek() != Some(&'{') {
+            out.push(c);
+            continue;
+        }
+
+        chars.next();
+
+        // Track brace depth so a nested `${...}` inside the name is kept
+        // intact for recursive expansion instead of closing the placeholder early.
+        let mut depth = 1;
+        let mut inner = String::new();
+        let mut closed = false;
+
+        while let Some(ch) = chars.next() {
+            if ch == '$' && chars.peek() == Some(&'{') {
+                chars.next();
+                depth += 1;
+                inner.push_str("${");
+                continue;
+            }
+
+            if ch == '}' {
+                depth -= 1;
+                if depth == 0 {
+                    closed = true;
+                    break;
+                }
+                inner.push('}');
+                continue;
+            }
+
+            inner.push(ch);
+        }
+
+        if !closed {
+            return Err(anyhow!("unterminated placeholder '${{{}'", inner));
+        }
+
+        let name = expand(&inner, config, strict)?;
+
+        match resolve(&name, config) {
+            Some(value) => out.push_str(&value),
+            None if strict => return Err(anyhow!("undefined placeholder '${{{}}}'", name)),
+            None => {
+                out.push_str("${");
+                out.push_str(&name);
+                out.push('}');
+            }
+        }
+    }
+
+    return Ok(out);
+}
+
+fn resolve(name: &str, config: &Config) -> Option<String> {
+    match name {
+        "PWD" => Some(config.pwd.display().to_string()),
+        "CONFIG_DIR" => config.config.parent().map(|dir| dir.display().to_string()),
+        _ => std::env::var(name).ok(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use std::path::PathBuf;
+
+    use crate::config::{Config, ConfigFormat, Operation};
+
+    use super::expand;
+
+    fn config(pwd: &str, config_path: &str) -> Config {
+        return Config {
+            operation: Operation::Print(None),
+            pwd: PathBuf::from(pwd),
+            config: PathBuf::from(config_path),
+            format: ConfigFormat::Json,
+        };
+    }
+
+    #[test]
+    fn test_expand_env_var() -> Result<()> {
+        std::env::set_var("PROJECTOR_TEST_EXPAND_VAR", "value");
+
+        let cfg = config("/home/user/project", "/home/user/.config/projector/projector.json");
+
+        assert_eq!(
+            expand("${PROJECTOR_TEST_EXPAND_VAR}", &cfg, false)?,
+            "value"
+        );
+
+        std::env::remove_var("PROJECTOR_TEST_EXPAND_VAR");
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_expand_pwd_and_config_dir() -> Result<()> {
+        let cfg = config("/home/user/project", "/home/user/.config/projector/projector.json");
+
+        assert_eq!(expand("${PWD}", &cfg, false)?, "/home/user/project");
+        assert_eq!(
+            expand("${CONFIG_DIR}", &cfg, false)?,
+            "/home/user/.config/projector"
+        );
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_expand_dollar_escape() -> Result<()> {
+        let cfg = config("/home/user/project", "/home/user/.config/projector/projector.json");
+
+        assert_eq!(expand("$$5", &cfg, false)?, "$5");
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_expand_missing_var_left_intact() -> Result<()> {
+        std::env::remove_var("PROJECTOR_TEST_MISSING_VAR");
+        let cfg = config("/home/user/project", "/home/user/.config/projector/projector.json");
+
+        assert_eq!(
+            expand("${PROJECTOR_TEST_MISSING_VAR}", &cfg, false)?,
+            "${PROJECTOR_TEST_MISSING_VAR}"
+        );
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_expand_missing_var_errors_when_strict() {
+        std::env::remove_var("PROJECTOR_TEST_MISSING_VAR");
+        let cfg = config("/home/user/project", "/home/user/.config/projector/projector.json");
+
+        let result = expand("${PROJECTOR_TEST_MISSING_VAR}", &cfg, true);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_expand_literal_braces_adjacent_to_placeholder() -> Result<()> {
+        let cfg = config("/home/user/project", "/home/user/.config/projector/projector.json");
+
+        assert_eq!(
+            expand("${PWD}/{literal}/${PWD}", &cfg, false)?,
+            "/home/user/project/{literal}//home/user/project"
+        );
+
+        return Ok(());
+    }
+
+    #[test]
+    fn test_expand_nested_placeholder() -> Result<()> {
+        std::env::set_var("PROJECTOR_TEST_NESTED_NAME", "PWD");
+
+        let cfg = config("/home/user/project", "/home/user/.config/projector/projector.json");
+
+        assert_eq!(
+            expand("${${PROJECTOR_TEST_NESTED_NAME}}", &cfg, false)?,
+            "/home/user/project"
+        );
+
+        std::env::remove_var("PROJECTOR_TEST_NESTED_NAME");
+
+        return Ok(());
+    }
+}